@@ -0,0 +1,115 @@
+use ::std::{fs, time::SystemTime};
+
+use ::serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Entry {
+    pub interpreter: String,
+    pub content: String,
+    pub args: Vec<String>,
+    pub run_count: u32,
+    pub last_run: u64,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct History {
+    entries: Vec<Entry>,
+    #[serde(skip)]
+    cursor: Option<usize>,
+}
+
+impl History {
+    const FILE_NAME: &'static str = "history.json";
+
+    pub fn load() -> Self {
+        let Some(path) = Self::path() else {
+            return Self::default();
+        };
+        let Ok(text) = fs::read_to_string(path) else {
+            return Self::default();
+        };
+        ::serde_json::from_str(&text).unwrap_or_default()
+    }
+
+    pub fn save(&self) {
+        let Some(path) = Self::path() else {
+            return;
+        };
+        if let Some(parent) = path.parent() {
+            if let Err(err) = fs::create_dir_all(parent) {
+                eprintln!("could not create history directory\n{err}");
+                return;
+            }
+        }
+        let text = match ::serde_json::to_string_pretty(self) {
+            Ok(text) => text,
+            Err(err) => {
+                eprintln!("could not serialize history\n{err}");
+                return;
+            }
+        };
+        if let Err(err) = fs::write(path, text) {
+            eprintln!("could not write history\n{err}");
+        }
+    }
+
+    fn path() -> Option<::std::path::PathBuf> {
+        let dirs = ::directories::ProjectDirs::from("", "", "batch-run")?;
+        Some(dirs.config_dir().join(Self::FILE_NAME))
+    }
+
+    fn now() -> u64 {
+        SystemTime::now()
+            .duration_since(SystemTime::UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or(0)
+    }
+
+    pub fn record(&mut self, interpreter: String, content: String, args: Vec<String>) {
+        self.cursor = None;
+
+        if let Some(pos) = self
+            .entries
+            .iter()
+            .position(|e| e.interpreter == interpreter && e.content == content && e.args == args)
+        {
+            let mut entry = self.entries.remove(pos);
+            entry.run_count += 1;
+            entry.last_run = Self::now();
+            self.entries.push(entry);
+        } else {
+            self.entries.push(Entry {
+                interpreter,
+                content,
+                args,
+                run_count: 1,
+                last_run: Self::now(),
+            });
+        }
+
+        self.save();
+    }
+
+    pub fn prev(&mut self) -> Option<&Entry> {
+        if self.entries.is_empty() {
+            return None;
+        }
+        self.cursor = Some(match self.cursor {
+            None => self.entries.len() - 1,
+            Some(0) => 0,
+            Some(idx) => idx - 1,
+        });
+        self.cursor.map(|idx| &self.entries[idx])
+    }
+
+    pub fn next(&mut self) -> Option<&Entry> {
+        let idx = self.cursor?;
+        if idx + 1 >= self.entries.len() {
+            self.cursor = None;
+            None
+        } else {
+            self.cursor = Some(idx + 1);
+            self.cursor.map(|idx| &self.entries[idx])
+        }
+    }
+}
@@ -0,0 +1,121 @@
+use ::std::{fs, path::Path};
+
+use ::serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Interpreter {
+    pub name: String,
+    pub executable: String,
+    pub args: Vec<String>,
+    #[serde(default)]
+    pub token: Option<String>,
+    #[serde(default)]
+    pub emulates: bool,
+}
+
+impl Interpreter {
+    pub fn token(&self) -> &str {
+        self.token.as_deref().unwrap_or(&self.name)
+    }
+
+    pub fn command_args(&self, batch: &str) -> Vec<String> {
+        self.args
+            .iter()
+            .map(|arg| {
+                if arg == "{batch}" {
+                    batch.to_owned()
+                } else {
+                    arg.clone()
+                }
+            })
+            .collect()
+    }
+}
+
+impl ::std::fmt::Display for Interpreter {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+        f.write_str(&self.name)
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Registry {
+    pub interpreter: Vec<Interpreter>,
+}
+
+impl Registry {
+    pub fn load(path: Option<&Path>) -> Self {
+        let Some(path) = path else {
+            return Self::default();
+        };
+        let text = match fs::read_to_string(path) {
+            Ok(text) => text,
+            Err(err) => {
+                eprintln!("could not read interpreter config\n{err}");
+                return Self::default();
+            }
+        };
+        match ::toml::from_str::<Self>(&text) {
+            Ok(registry) if !registry.interpreter.is_empty() => registry,
+            Ok(_) => {
+                eprintln!("interpreter config at {} has no entries", path.display());
+                Self::default()
+            }
+            Err(err) => {
+                eprintln!("could not parse interpreter config\n{err}");
+                Self::default()
+            }
+        }
+    }
+}
+
+impl Default for Registry {
+    fn default() -> Self {
+        Self {
+            interpreter: vec![
+                Interpreter {
+                    name: "zsh".to_owned(),
+                    executable: "zsh".to_owned(),
+                    args: vec![
+                        "--emulate".to_owned(),
+                        "zsh".to_owned(),
+                        "-c".to_owned(),
+                        "{batch}".to_owned(),
+                        "batch-script-zsh".to_owned(),
+                    ],
+                    token: None,
+                    emulates: true,
+                },
+                Interpreter {
+                    name: "bash".to_owned(),
+                    executable: "bash".to_owned(),
+                    args: vec![
+                        "-c".to_owned(),
+                        "{batch}".to_owned(),
+                        "batch-script-bash".to_owned(),
+                    ],
+                    token: None,
+                    emulates: false,
+                },
+                Interpreter {
+                    name: "python".to_owned(),
+                    executable: "python".to_owned(),
+                    args: vec!["-c".to_owned(), "{batch}".to_owned()],
+                    token: None,
+                    emulates: false,
+                },
+                Interpreter {
+                    name: "sh".to_owned(),
+                    executable: "sh".to_owned(),
+                    args: vec![
+                        "-c".to_owned(),
+                        "{batch}".to_owned(),
+                        "batch-script-sh".to_owned(),
+                    ],
+                    token: None,
+                    emulates: false,
+                },
+            ],
+        }
+    }
+}
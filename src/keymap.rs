@@ -0,0 +1,203 @@
+use ::std::{fs, path::Path};
+
+use ::derive_more::Display;
+use ::iced::keyboard::{Key, Modifiers, key::Named};
+use ::serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Display, ::clap::ValueEnum, Serialize, Deserialize)]
+pub enum EditMode {
+    #[display("emacs")]
+    Emacs,
+    #[display("vi")]
+    Vi,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Display)]
+pub enum ViMode {
+    #[display("normal")]
+    Normal,
+    #[display("insert")]
+    Insert,
+}
+
+impl Default for ViMode {
+    fn default() -> Self {
+        Self::Insert
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum BindingMode {
+    Emacs,
+    ViNormal,
+    ViInsert,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum KeyAction {
+    Run,
+    InsertTab,
+    HistoryPrev,
+    HistoryNext,
+    ViNormalMode,
+    ViInsertMode,
+    MoveLeft,
+    MoveRight,
+    MoveUp,
+    MoveDown,
+    DeleteChar,
+    AppendEnd,
+    InsertLineStart,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Chord {
+    pub key: String,
+    #[serde(default)]
+    pub ctrl: bool,
+    #[serde(default)]
+    pub shift: bool,
+    #[serde(default)]
+    pub alt: bool,
+}
+
+impl Chord {
+    fn new(key: &str) -> Self {
+        Self {
+            key: key.to_owned(),
+            ctrl: false,
+            shift: false,
+            alt: false,
+        }
+    }
+
+    fn ctrl(mut self) -> Self {
+        self.ctrl = true;
+        self
+    }
+
+    fn shift(mut self) -> Self {
+        self.shift = true;
+        self
+    }
+
+    fn modifiers(&self) -> Modifiers {
+        let mut modifiers = Modifiers::empty();
+        if self.ctrl {
+            modifiers |= Modifiers::CTRL;
+        }
+        if self.shift {
+            modifiers |= Modifiers::SHIFT;
+        }
+        if self.alt {
+            modifiers |= Modifiers::ALT;
+        }
+        modifiers
+    }
+
+    fn named(name: &str) -> Option<Named> {
+        Some(match name {
+            "Tab" => Named::Tab,
+            "Escape" => Named::Escape,
+            "ArrowUp" => Named::ArrowUp,
+            "ArrowDown" => Named::ArrowDown,
+            "ArrowLeft" => Named::ArrowLeft,
+            "ArrowRight" => Named::ArrowRight,
+            _ => return None,
+        })
+    }
+
+    fn matches(&self, key: &Key, modifiers: Modifiers) -> bool {
+        if modifiers != self.modifiers() {
+            return false;
+        }
+        match Self::named(&self.key) {
+            Some(named) => matches!(key, Key::Named(k) if *k == named),
+            None => key.as_ref() == Key::Character(self.key.as_str()),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Binding {
+    pub mode: BindingMode,
+    pub chord: Chord,
+    pub action: KeyAction,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Keymap {
+    pub bindings: Vec<Binding>,
+}
+
+impl Keymap {
+    pub fn load(path: Option<&Path>) -> Self {
+        let Some(path) = path else {
+            return Self::default();
+        };
+        let text = match fs::read_to_string(path) {
+            Ok(text) => text,
+            Err(err) => {
+                eprintln!("could not read keymap config\n{err}");
+                return Self::default();
+            }
+        };
+        match ::toml::from_str(&text) {
+            Ok(keymap) => keymap,
+            Err(err) => {
+                eprintln!("could not parse keymap config\n{err}");
+                Self::default()
+            }
+        }
+    }
+
+    pub fn lookup(&self, mode: BindingMode, key: &Key, modifiers: Modifiers) -> Option<KeyAction> {
+        self.bindings
+            .iter()
+            .find(|binding| binding.mode == mode && binding.chord.matches(key, modifiers))
+            .map(|binding| binding.action)
+    }
+}
+
+impl Default for Keymap {
+    fn default() -> Self {
+        use BindingMode::{Emacs, ViInsert, ViNormal};
+        use KeyAction::*;
+
+        let bind = |mode, chord: Chord, action| Binding {
+            mode,
+            chord,
+            action,
+        };
+
+        Self {
+            bindings: vec![
+                // Available regardless of edit mode.
+                bind(Emacs, Chord::new("Tab"), InsertTab),
+                bind(Emacs, Chord::new("r").ctrl(), Run),
+                bind(Emacs, Chord::new("ArrowUp").ctrl(), HistoryPrev),
+                bind(Emacs, Chord::new("ArrowDown").ctrl(), HistoryNext),
+                bind(ViInsert, Chord::new("Tab"), InsertTab),
+                bind(ViInsert, Chord::new("r").ctrl(), Run),
+                bind(ViInsert, Chord::new("ArrowUp").ctrl(), HistoryPrev),
+                bind(ViInsert, Chord::new("ArrowDown").ctrl(), HistoryNext),
+                bind(ViNormal, Chord::new("r").ctrl(), Run),
+                bind(ViNormal, Chord::new("ArrowUp").ctrl(), HistoryPrev),
+                bind(ViNormal, Chord::new("ArrowDown").ctrl(), HistoryNext),
+                // Vi: leaving insert mode.
+                bind(ViInsert, Chord::new("Escape"), ViNormalMode),
+                // Vi: motions and operators in normal mode.
+                bind(ViNormal, Chord::new("h"), MoveLeft),
+                bind(ViNormal, Chord::new("l"), MoveRight),
+                bind(ViNormal, Chord::new("k"), MoveUp),
+                bind(ViNormal, Chord::new("j"), MoveDown),
+                bind(ViNormal, Chord::new("x"), DeleteChar),
+                bind(ViNormal, Chord::new("i"), ViInsertMode),
+                bind(ViNormal, Chord::new("A").shift(), AppendEnd),
+                bind(ViNormal, Chord::new("I").shift(), InsertLineStart),
+            ],
+        }
+    }
+}
@@ -1,38 +1,172 @@
-use ::std::{
-    io::{Write, pipe},
-    num::NonZero,
-    process::ExitCode,
-};
+use ::std::ffi::OsString;
+use ::std::num::NonZero;
+use ::std::path::PathBuf;
+use ::std::process::{ExitCode, ExitStatus, Stdio};
+use ::std::sync::Arc;
+
+mod history;
+mod interpreters;
+mod keymap;
+
+use self::interpreters::{Interpreter, Registry};
+use self::keymap::{BindingMode, EditMode, KeyAction, Keymap, ViMode};
 
+use ::bytes::BytesMut;
 use ::clap::Parser;
 use ::derive_more::Display;
 use ::iced::{
     Element, Length, Task, Theme, application,
-    keyboard::{Key, Modifiers},
+    futures::{SinkExt, Stream, stream},
     widget::{
         self, Column, Row,
-        text_editor::{self, Action, Binding, Edit},
+        text_editor::{self, Action, Binding, Edit, Motion},
     },
 };
 use ::iced_highlighter::Highlighter;
-use ::serde::Serialize;
-use ::strum::VariantArray;
-use ::tokio::process::Command;
+use ::serde::{Deserialize, Serialize};
+use ::tokio::{
+    io::AsyncReadExt,
+    process::{Child, ChildStderr, ChildStdin, ChildStdout, Command},
+};
+use ::tokio_util::codec::{Encoder, FramedWrite};
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "kind", content = "data", rename_all = "snake_case")]
+enum ArgValue {
+    Text(String),
+    Binary(Vec<u8>),
+}
+
+impl ArgValue {
+    fn from_os(arg: OsString) -> Self {
+        match arg.into_string() {
+            Ok(text) => ArgValue::Text(text),
+            Err(os) => ArgValue::Binary(os.as_encoded_bytes().to_vec()),
+        }
+    }
+
+    fn len(&self) -> usize {
+        match self {
+            ArgValue::Text(text) => text.len(),
+            ArgValue::Binary(bytes) => bytes.len(),
+        }
+    }
+
+    fn reversed(&self) -> Option<String> {
+        match self {
+            ArgValue::Text(text) => Some(text.chars().rev().collect()),
+            ArgValue::Binary(_) => None,
+        }
+    }
+}
 
 #[derive(Debug, Clone, Serialize)]
-struct InputData<'i> {
-    line: &'i str,
+struct InputData {
+    #[serde(flatten)]
+    value: ArgValue,
     idx: usize,
     len: usize,
-    reversed: String,
+    reversed: Option<String>,
+}
+
+impl InputData {
+    fn new(idx: usize, value: ArgValue) -> Self {
+        Self {
+            len: value.len(),
+            reversed: value.reversed(),
+            value,
+            idx,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(tag = "method", content = "params", rename_all = "snake_case")]
+enum Request {
+    GetArg { idx: usize },
+    ArgCount,
+    Meta,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct UnknownRequest {
+    method: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+struct RpcMeta {
+    tabwidth: u8,
+    interpreter: String,
+    emulates: bool,
+    arg_count: usize,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(untagged)]
+enum ResponseBody {
+    Arg(Option<InputData>),
+    ArgCount(usize),
+    Meta(RpcMeta),
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(untagged)]
+enum Response {
+    Result { result: ResponseBody },
+    Error { error: String },
+}
+
+fn dispatch(req: Request, args: &[OsString], tabwidth: u8, interpreter: &Interpreter) -> Response {
+    let result = match req {
+        Request::GetArg { idx } => ResponseBody::Arg(
+            args.get(idx)
+                .map(|arg| InputData::new(idx, ArgValue::from_os(arg.clone()))),
+        ),
+        Request::ArgCount => ResponseBody::ArgCount(args.len()),
+        Request::Meta => ResponseBody::Meta(RpcMeta {
+            tabwidth,
+            interpreter: interpreter.name.clone(),
+            emulates: interpreter.emulates,
+            arg_count: args.len(),
+        }),
+    };
+    Response::Result { result }
+}
+
+struct ResponseCodec;
+
+impl Encoder<Response> for ResponseCodec {
+    type Error = ::std::io::Error;
+
+    fn encode(&mut self, item: Response, dst: &mut BytesMut) -> Result<(), Self::Error> {
+        let bytes = ::serde_json::to_vec(&item).map_err(::std::io::Error::other)?;
+        dst.extend_from_slice(&bytes);
+        dst.extend_from_slice(b"\n");
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, Copy, Display, PartialEq, Eq)]
+enum StdKind {
+    #[display("stdout")]
+    Stdout,
+    #[display("stderr")]
+    Stderr,
 }
 
 #[derive(Debug)]
 struct App {
     cli: Cli,
     content: text_editor::Content,
+    output: text_editor::Content,
+    output_ends_with_newline: bool,
     settings: ::iced_highlighter::Settings,
-    language: Language,
+    registry: Registry,
+    interpreter: Interpreter,
+    history: history::History,
+    keymap: Keymap,
+    edit_mode: EditMode,
+    vi_mode: ViMode,
 }
 
 #[derive(Debug, Parser)]
@@ -41,28 +175,335 @@ struct Cli {
     #[arg(long, default_value_t = 4)]
     tabwidth: u8,
 
-    /// Arguments to pass as stdin to batch script.
-    args: Vec<String>,
+    /// Path to a TOML file of interpreters, overriding the built-in
+    /// defaults (zsh, bash, python, sh).
+    #[arg(long)]
+    config: Option<PathBuf>,
+
+    /// Editing mode: emacs-style (default) or modal vi.
+    #[arg(long, value_enum, default_value_t = EditMode::Emacs)]
+    edit_mode: EditMode,
+
+    /// Path to a TOML file of keybindings, overriding the built-in defaults.
+    #[arg(long)]
+    keymap: Option<PathBuf>,
+
+    /// Arguments the batch script can request over its JSON-RPC pipe, text or
+    /// binary.
+    args: Vec<OsString>,
 }
 
 #[derive(Debug, Clone)]
 enum Msg {
     ContentAction(Action),
-    Language(Language),
+    Interpreter(Interpreter),
     InsertTab,
     Run,
+    Output { stream: StdKind, bytes: Vec<u8> },
+    Finished(ExitStatus),
+    HistoryPrev,
+    HistoryNext,
+    ViMode(ViMode),
+    ViAppendEnd,
+    ViInsertLineStart,
+}
+
+fn key_action_to_msg(action: KeyAction) -> Msg {
+    match action {
+        KeyAction::Run => Msg::Run,
+        KeyAction::InsertTab => Msg::InsertTab,
+        KeyAction::HistoryPrev => Msg::HistoryPrev,
+        KeyAction::HistoryNext => Msg::HistoryNext,
+        KeyAction::ViNormalMode => Msg::ViMode(ViMode::Normal),
+        KeyAction::ViInsertMode => Msg::ViMode(ViMode::Insert),
+        KeyAction::MoveLeft => Msg::ContentAction(Action::Move(Motion::Left)),
+        KeyAction::MoveRight => Msg::ContentAction(Action::Move(Motion::Right)),
+        KeyAction::MoveUp => Msg::ContentAction(Action::Move(Motion::Up)),
+        KeyAction::MoveDown => Msg::ContentAction(Action::Move(Motion::Down)),
+        KeyAction::DeleteChar => Msg::ContentAction(Action::Edit(Edit::Delete)),
+        KeyAction::AppendEnd => Msg::ViAppendEnd,
+        KeyAction::InsertLineStart => Msg::ViInsertLineStart,
+    }
+}
+
+fn spawn_interpreter(interpreter: &Interpreter, batch: &str) -> ::std::io::Result<Child> {
+    Command::new(&interpreter.executable)
+        .args(interpreter.command_args(batch))
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+}
+
+fn take_line(buf: &mut Vec<u8>) -> Option<Vec<u8>> {
+    let pos = buf.iter().position(|&b| b == b'\n')?;
+    let rest = buf.split_off(pos + 1);
+    let mut line = ::std::mem::replace(buf, rest);
+    line.pop();
+    Some(line)
 }
 
-#[derive(Debug, Clone, Copy, Display, PartialEq, Eq, VariantArray)]
-enum Language {
-    #[display("zsh")]
-    Zsh,
-    #[display("bash")]
-    Bash,
-    #[display("python")]
-    Python,
-    #[display("sh")]
-    Sh,
+enum RunState {
+    Start {
+        interpreter: Interpreter,
+        tabwidth: u8,
+        batch: String,
+        args: Vec<OsString>,
+    },
+    Reading {
+        child: Child,
+        stdin: FramedWrite<ChildStdin, ResponseCodec>,
+        stdout: ChildStdout,
+        stderr: ChildStderr,
+        stdout_buf: Vec<u8>,
+        stderr_buf: Vec<u8>,
+        stdout_done: bool,
+        stderr_done: bool,
+        tabwidth: u8,
+        interpreter: Interpreter,
+        args: Vec<OsString>,
+    },
+    Waiting {
+        child: Child,
+    },
+    Done,
+}
+
+async fn run_step(mut state: RunState) -> Option<(Msg, RunState)> {
+    loop {
+        state = match state {
+            RunState::Start {
+                interpreter,
+                tabwidth,
+                batch,
+                args,
+            } => {
+                let mut child = match spawn_interpreter(&interpreter, &batch) {
+                    Ok(child) => child,
+                    Err(err) => {
+                        eprintln!("could not spawn child\n{err}");
+                        return None;
+                    }
+                };
+
+                let (Some(stdin), Some(stdout), Some(stderr)) =
+                    (child.stdin.take(), child.stdout.take(), child.stderr.take())
+                else {
+                    eprintln!("child is missing a piped stream");
+                    return None;
+                };
+
+                RunState::Reading {
+                    child,
+                    stdin: FramedWrite::new(stdin, ResponseCodec),
+                    stdout,
+                    stderr,
+                    stdout_buf: Vec::new(),
+                    stderr_buf: Vec::new(),
+                    stdout_done: false,
+                    stderr_done: false,
+                    tabwidth,
+                    interpreter,
+                    args,
+                }
+            }
+            RunState::Reading {
+                child,
+                mut stdin,
+                mut stdout,
+                mut stderr,
+                mut stdout_buf,
+                mut stderr_buf,
+                mut stdout_done,
+                mut stderr_done,
+                tabwidth,
+                interpreter,
+                args,
+            } => {
+                if let Some(line) = take_line(&mut stdout_buf) {
+                    let response = match ::serde_json::from_slice::<Request>(&line) {
+                        Ok(req) => dispatch(req, &args, tabwidth, &interpreter),
+                        Err(_) => match ::serde_json::from_slice::<UnknownRequest>(&line) {
+                            Ok(unknown) => Response::Error {
+                                error: format!("unknown method: {}", unknown.method),
+                            },
+                            Err(_) => {
+                                let mut bytes = line;
+                                bytes.push(b'\n');
+                                return Some((
+                                    Msg::Output {
+                                        stream: StdKind::Stdout,
+                                        bytes,
+                                    },
+                                    RunState::Reading {
+                                        child,
+                                        stdin,
+                                        stdout,
+                                        stderr,
+                                        stdout_buf,
+                                        stderr_buf,
+                                        stdout_done,
+                                        stderr_done,
+                                        tabwidth,
+                                        interpreter,
+                                        args,
+                                    },
+                                ));
+                            }
+                        },
+                    };
+
+                    if let Err(err) = stdin.send(response).await {
+                        eprintln!("could not write response to child stdin\n{err}");
+                    }
+
+                    RunState::Reading {
+                        child,
+                        stdin,
+                        stdout,
+                        stderr,
+                        stdout_buf,
+                        stderr_buf,
+                        stdout_done,
+                        stderr_done,
+                        tabwidth,
+                        interpreter,
+                        args,
+                    }
+                } else if let Some(mut bytes) = take_line(&mut stderr_buf) {
+                    bytes.push(b'\n');
+                    return Some((
+                        Msg::Output {
+                            stream: StdKind::Stderr,
+                            bytes,
+                        },
+                        RunState::Reading {
+                            child,
+                            stdin,
+                            stdout,
+                            stderr,
+                            stdout_buf,
+                            stderr_buf,
+                            stdout_done,
+                            stderr_done,
+                            tabwidth,
+                            interpreter,
+                            args,
+                        },
+                    ));
+                } else if stdout_done && stderr_done {
+                    if !stdout_buf.is_empty() {
+                        let bytes = ::std::mem::take(&mut stdout_buf);
+                        return Some((
+                            Msg::Output {
+                                stream: StdKind::Stdout,
+                                bytes,
+                            },
+                            RunState::Reading {
+                                child,
+                                stdin,
+                                stdout,
+                                stderr,
+                                stdout_buf,
+                                stderr_buf,
+                                stdout_done,
+                                stderr_done,
+                                tabwidth,
+                                interpreter,
+                                args,
+                            },
+                        ));
+                    }
+                    if !stderr_buf.is_empty() {
+                        let bytes = ::std::mem::take(&mut stderr_buf);
+                        return Some((
+                            Msg::Output {
+                                stream: StdKind::Stderr,
+                                bytes,
+                            },
+                            RunState::Reading {
+                                child,
+                                stdin,
+                                stdout,
+                                stderr,
+                                stdout_buf,
+                                stderr_buf,
+                                stdout_done,
+                                stderr_done,
+                                tabwidth,
+                                interpreter,
+                                args,
+                            },
+                        ));
+                    }
+                    drop(stdin);
+                    RunState::Waiting { child }
+                } else {
+                    let mut buf = [0u8; 4096];
+                    let read = ::tokio::select! {
+                        n = stdout.read(&mut buf), if !stdout_done => (StdKind::Stdout, n),
+                        n = stderr.read(&mut buf), if !stderr_done => (StdKind::Stderr, n),
+                    };
+
+                    match read {
+                        (StdKind::Stdout, Ok(0)) => stdout_done = true,
+                        (StdKind::Stderr, Ok(0)) => stderr_done = true,
+                        (StdKind::Stdout, Err(err)) => {
+                            eprintln!("could not read child stdout\n{err}");
+                            stdout_done = true;
+                        }
+                        (StdKind::Stderr, Err(err)) => {
+                            eprintln!("could not read child stderr\n{err}");
+                            stderr_done = true;
+                        }
+                        (StdKind::Stdout, Ok(n)) => stdout_buf.extend_from_slice(&buf[..n]),
+                        (StdKind::Stderr, Ok(n)) => stderr_buf.extend_from_slice(&buf[..n]),
+                    }
+
+                    RunState::Reading {
+                        child,
+                        stdin,
+                        stdout,
+                        stderr,
+                        stdout_buf,
+                        stderr_buf,
+                        stdout_done,
+                        stderr_done,
+                        tabwidth,
+                        interpreter,
+                        args,
+                    }
+                }
+            }
+            RunState::Waiting { mut child } => {
+                return match child.wait().await {
+                    Ok(status) => Some((Msg::Finished(status), RunState::Done)),
+                    Err(err) => {
+                        eprintln!("could not wait on child\n{err}");
+                        None
+                    }
+                };
+            }
+            RunState::Done => return None,
+        }
+    }
+}
+
+fn run_batch(
+    interpreter: Interpreter,
+    tabwidth: u8,
+    batch: String,
+    args: Vec<OsString>,
+) -> impl Stream<Item = Msg> {
+    stream::unfold(
+        RunState::Start {
+            interpreter,
+            tabwidth,
+            batch,
+            args,
+        },
+        run_step,
+    )
 }
 
 impl App {
@@ -83,123 +524,165 @@ impl App {
                 Task::none()
             }
             Msg::Run => {
+                self.output = text_editor::Content::new();
+                self.output_ends_with_newline = true;
                 let batch = self.content.text();
-                let lang = self.language;
+                let interpreter = self.interpreter.clone();
+                let tabwidth = self.cli.tabwidth;
                 let args = self.cli.args.clone();
-                Task::future(async move {
-                    let (r, mut w) = match pipe() {
-                        Ok(pipe) => pipe,
-                        Err(err) => {
-                            eprintln!("could not create pipe\n{err}");
-                            return;
-                        }
-                    };
-                    let result = match lang {
-                        Language::Zsh => Command::new("/usr/bin/zsh")
-                            .args(["--emulate", "zsh", "-c"])
-                            .arg(batch)
-                            .arg("batch-script-zsh")
-                            .stdin(r)
-                            .spawn(),
-                        Language::Bash => Command::new("/usr/bin/bash")
-                            .arg("-c")
-                            .arg(batch)
-                            .arg("batch-script-bash")
-                            .stdin(r)
-                            .spawn(),
-                        Language::Python => Command::new("/usr/bin/python")
-                            .arg("-c")
-                            .arg(batch)
-                            .stdin(r)
-                            .spawn(),
-                        Language::Sh => Command::new("/usr/bin/sh")
-                            .arg("-c")
-                            .arg(batch)
-                            .arg("batch-script-sh")
-                            .stdin(r)
-                            .spawn(),
-                    };
-                    let mut child = match result {
-                        Ok(child) => child,
-                        Err(err) => {
-                            eprintln!("could not spawn child\n{err}");
-                            return;
-                        }
-                    };
-
-                    for (idx, arg) in args.into_iter().enumerate() {
-                        let data = InputData {
-                            line: &arg,
-                            idx,
-                            len: arg.len(),
-                            reversed: arg.chars().rev().collect(),
-                        };
-
-                        let r = ::serde_json::to_writer(&mut w, &data);
-                        if let Err(err) = r {
-                            eprintln!("could not write data to pipe\n{err}");
-                            return;
-                        }
-                        if let Err(err) = w.write_all(b"\n") {
-                            eprintln!("could not terminate data written to pipe\n{err}");
-                            return;
-                        }
-                    }
 
-                    if let Err(err) = w.flush() {
-                        eprintln!("could not flush pipe\n{err}");
-                        return;
-                    }
-                    drop(w);
+                let arg_strings = args
+                    .iter()
+                    .map(|arg| arg.to_string_lossy().into_owned())
+                    .collect();
+                self.history
+                    .record(interpreter.name.clone(), batch.clone(), arg_strings);
 
-                    match child.wait().await {
-                        Ok(status) => eprintln!("{status}"),
-                        Err(err) => eprintln!("could not wait on child\n{err}"),
-                    }
-                })
-                .then(|_| Task::none())
+                Task::stream(run_batch(interpreter, tabwidth, batch, args))
+            }
+            Msg::Interpreter(interpreter) => {
+                self.settings.token = interpreter.token().to_owned();
+                self.interpreter = interpreter;
+                Task::none()
+            }
+            Msg::Output { stream, bytes } => {
+                self.output_ends_with_newline = bytes.last() == Some(&b'\n');
+                let text = String::from_utf8_lossy(&bytes);
+                let tagged = format!("[{stream}] {text}");
+                self.output.perform(Action::Edit(Edit::Paste(Arc::from(tagged))));
+                Task::none()
+            }
+            Msg::Finished(status) => {
+                if !self.output_ends_with_newline {
+                    self.output.perform(Action::Edit(Edit::Enter));
+                }
+                for c in format!("-- exited: {status} --").chars() {
+                    self.output.perform(Action::Edit(Edit::Insert(c)));
+                }
+                self.output.perform(Action::Edit(Edit::Enter));
+                Task::none()
+            }
+            Msg::HistoryPrev => {
+                let entry = self
+                    .history
+                    .prev()
+                    .map(|e| (e.interpreter.clone(), e.content.clone()));
+                if let Some((interpreter, content)) = entry {
+                    self.apply_history_entry(interpreter, content);
+                }
+                Task::none()
+            }
+            Msg::HistoryNext => {
+                let entry = self
+                    .history
+                    .next()
+                    .map(|e| (e.interpreter.clone(), e.content.clone()));
+                if let Some((interpreter, content)) = entry {
+                    self.apply_history_entry(interpreter, content);
+                }
+                Task::none()
             }
-            Msg::Language(language) => {
-                self.language = language;
-                self.settings.token = language.to_string();
+            Msg::ViMode(mode) => {
+                self.vi_mode = mode;
                 Task::none()
             }
+            Msg::ViAppendEnd => {
+                self.content.perform(Action::Move(Motion::End));
+                self.vi_mode = ViMode::Insert;
+                Task::none()
+            }
+            Msg::ViInsertLineStart => {
+                self.content.perform(Action::Move(Motion::Home));
+                self.vi_mode = ViMode::Insert;
+                Task::none()
+            }
+        }
+    }
+
+    fn apply_history_entry(&mut self, interpreter_name: String, content: String) {
+        if let Some(interpreter) = self
+            .registry
+            .interpreter
+            .iter()
+            .find(|i| i.name == interpreter_name)
+        {
+            self.settings.token = interpreter.token().to_owned();
+            self.interpreter = interpreter.clone();
+        }
+        self.content = text_editor::Content::with_text(&content);
+    }
+
+    fn binding_mode(&self) -> BindingMode {
+        match self.edit_mode {
+            EditMode::Emacs => BindingMode::Emacs,
+            EditMode::Vi => match self.vi_mode {
+                ViMode::Normal => BindingMode::ViNormal,
+                ViMode::Insert => BindingMode::ViInsert,
+            },
         }
     }
 
     pub fn view(&self) -> Element<'_, Msg> {
+        let mode = self.binding_mode();
+        let mode_label = match self.edit_mode {
+            EditMode::Emacs => self.edit_mode.to_string(),
+            EditMode::Vi => format!("{}:{}", self.edit_mode, self.vi_mode),
+        };
+
         Column::new()
-            .push(Row::new().push(widget::pick_list(
-                Language::VARIANTS,
-                Some(self.language),
-                Msg::Language,
-            )))
+            .push(
+                Row::new()
+                    .push(widget::pick_list(
+                        self.registry.interpreter.as_slice(),
+                        Some(self.interpreter.clone()),
+                        Msg::Interpreter,
+                    ))
+                    .push(widget::text(mode_label)),
+            )
             .push(
                 widget::text_editor(&self.content)
                     .on_action(Msg::ContentAction)
-                    .height(Length::Fill)
+                    .height(Length::FillPortion(3))
                     .font(::iced::Font::MONOSPACE)
                     .highlight_with::<Highlighter>(self.settings.clone(), |h, _| h.to_format())
-                    .key_binding(|keypress| {
-                        if keypress.modifiers.is_empty()
-                            && matches!(keypress.key, Key::Named(::iced::keyboard::key::Named::Tab))
-                        {
-                            Some(Binding::Custom(Msg::InsertTab))
-                        } else if keypress.modifiers == Modifiers::CTRL
-                            && keypress.key.as_ref() == Key::Character("r")
+                    .key_binding(move |keypress| {
+                        if let Some(action) =
+                            self.keymap
+                                .lookup(mode, &keypress.key, keypress.modifiers)
                         {
-                            Some(Binding::Custom(Msg::Run))
+                            Some(Binding::Custom(key_action_to_msg(action)))
+                        } else if mode == BindingMode::ViNormal {
+                            // Normal mode is non-inserting: swallow unbound
+                            // keys instead of falling back to default edits.
+                            None
                         } else {
                             Binding::from_key_press(keypress)
                         }
                     }),
             )
+            .push(
+                widget::text_editor(&self.output)
+                    .height(Length::FillPortion(1))
+                    .font(::iced::Font::MONOSPACE),
+            )
             .into()
     }
 }
 
 fn main() -> ExitCode {
     let cli = Cli::parse();
+    let registry = Registry::load(cli.config.as_deref());
+    let interpreter = registry
+        .interpreter
+        .first()
+        .cloned()
+        .expect("registry always has at least one interpreter");
+    let keymap = Keymap::load(cli.keymap.as_deref());
+    let edit_mode = cli.edit_mode;
+    let vi_mode = match edit_mode {
+        EditMode::Vi => ViMode::Normal,
+        EditMode::Emacs => ViMode::default(),
+    };
     match application("Batch Run", App::update, App::view)
         .theme(|_| Theme::SolarizedDark)
         .run_with(move || {
@@ -208,10 +691,17 @@ fn main() -> ExitCode {
                     cli,
                     settings: ::iced_highlighter::Settings {
                         theme: ::iced_highlighter::Theme::SolarizedDark,
-                        token: "zsh".to_owned(),
+                        token: interpreter.token().to_owned(),
                     },
-                    language: Language::Zsh,
+                    registry,
+                    interpreter,
                     content: Default::default(),
+                    output: Default::default(),
+                    output_ends_with_newline: true,
+                    history: history::History::load(),
+                    keymap,
+                    edit_mode,
+                    vi_mode,
                 },
                 Task::none(),
             )